@@ -31,6 +31,26 @@ impl Config {
         }
     }
 
+    /// # Merge
+    /// Merge ``other`` into this ``Config``. For groups that exist in both, keys
+    /// present in ``other`` override the matching keys in ``self`` while every other
+    /// key is left untouched. Groups that only exist in ``other`` are added as-is.
+    /// This is how layered configuration files (base + environment overrides) are composed.
+    pub fn merge(&mut self, other: Config) {
+        for (name, group) in other.map {
+            match self.map.get_mut(&name) {
+                Some(existing) => {
+                    for (key, item) in group.pairs {
+                        existing.pairs.insert(key, item);
+                    }
+                },
+                _ => {
+                    self.map.insert(name, group);
+                },
+            }
+        }
+    }
+
     /// # Add group
     /// Insert a new group into the ``Config`` instance.
     /// This is mostly used by the internal parser functions, but can also be
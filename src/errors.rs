@@ -9,17 +9,46 @@ pub enum ConfigReadError {
     /// or isn't found where expected
     FileNotFound,
 
-    /// Invalid syntax on line
+    /// Invalid syntax
     ///
     /// One or more lines in the configuration file has an invalid syntax, i.e.
-    /// it's not a comment, not a group title or a configuration item
-    InvalidSyntaxOnLine,
+    /// it's not a comment, not a group title or a configuration item.
+    /// ``line`` is the 1-based line number and ``content`` is the offending line,
+    /// with leading and trailing whitespace trimmed.
+    InvalidSyntax {
+        line: usize,
+        content: String,
+    },
+
+    /// Include cycle
+    ///
+    /// Returned when a configuration file includes itself, directly or transitively,
+    /// through one or more ``include`` directives
+    IncludeCycle,
+}
+
+impl std::fmt::Display for ConfigReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileNotFound => write!(f, "config file not found"),
+            InvalidSyntax { line, content } => write!(f, "config error at line {}: \"{}\"", line, content),
+            IncludeCycle => write!(f, "config include cycle detected"),
+        }
+    }
 }
 
+use ConfigReadError::*;
+
 /// # Errors in configuration values
 #[derive(Debug)]
 pub enum ConfigValueError {
     /// An InvalidBoolValue is when the configuration value is none of the following:
     /// 1, 0, true, false, on, off, yes, no
     InvalidBoolValue,
+
+    /// An InvalidIntValue is when the configuration value can't be parsed as an integer
+    InvalidIntValue,
+
+    /// An InvalidFloatValue is when the configuration value can't be parsed as a floating-point number
+    InvalidFloatValue,
 }
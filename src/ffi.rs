@@ -0,0 +1,131 @@
+//! C FFI surface.
+//!
+//! Lets the parser be driven from C/C++ (or any other language with a C FFI).
+//! Errors are surfaced as a ``null`` return plus a thread-local last-error string
+//! retrievable via ``config_last_error``.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::{Config, ConfigReadError, ConfigReader};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(err: &ConfigReadError) {
+    let message = CString::new(err.to_string())
+        .unwrap_or_else(|_| CString::new("unknown error").unwrap());
+    LAST_ERROR.with(|cell| *cell.borrow_mut() = Some(message));
+}
+
+/// # Read a configuration file
+/// Returns a pointer to a heap-allocated ``Config`` on success, or ``null`` on failure.
+/// When the file can't be read or parsed, the reason is retrievable via
+/// ``config_last_error``; a null or non-UTF-8 ``path`` also returns ``null`` but records
+/// no error. The returned pointer must eventually be released with ``config_free``.
+///
+/// # Safety
+/// ``path`` must be either null or a pointer to a valid, NUL-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn config_reader_read(path: *const c_char) -> *mut Config {
+    if path.is_null() {
+        return ptr::null_mut();
+    }
+
+    let path_str = match unsafe { CStr::from_ptr(path) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return ptr::null_mut(),
+    };
+
+    match ConfigReader::read(path_str, None) {
+        Ok(config) => Box::into_raw(Box::new(config)),
+        Err(e) => {
+            set_last_error(&e);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// # Get a configuration value
+/// Returns a newly allocated C string with the value of ``group``/``key``, or ``null``
+/// if either doesn't exist. The returned string must be released with
+/// ``config_string_free``.
+///
+/// # Safety
+/// ``cfg`` must be either null or a pointer previously returned by
+/// ``config_reader_read`` and not yet passed to ``config_free``. ``group`` and ``key``
+/// must be either null or pointers to valid, NUL-terminated C strings.
+#[no_mangle]
+pub unsafe extern "C" fn config_get(
+    cfg: *const Config,
+    group: *const c_char,
+    key: *const c_char,
+) -> *mut c_char {
+    if cfg.is_null() || group.is_null() || key.is_null() {
+        return ptr::null_mut();
+    }
+
+    let config = unsafe { &*cfg };
+
+    let group_str = match unsafe { CStr::from_ptr(group) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return ptr::null_mut(),
+    };
+
+    let key_str = match unsafe { CStr::from_ptr(key) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return ptr::null_mut(),
+    };
+
+    let value = config.group(group_str)
+        .and_then(|g| g.get(key_str))
+        .map(|item| item.value);
+
+    match value {
+        Some(v) => CString::new(v).map(CString::into_raw).unwrap_or(ptr::null_mut()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// # Free a ``Config``
+/// Releases a ``Config`` previously returned by ``config_reader_read``.
+/// Passing ``null`` is a no-op.
+///
+/// # Safety
+/// ``cfg`` must be either null or a pointer previously returned by
+/// ``config_reader_read``, not already freed, and not used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn config_free(cfg: *mut Config) {
+    if !cfg.is_null() {
+        unsafe { drop(Box::from_raw(cfg)); }
+    }
+}
+
+/// # Free a string
+/// Releases a string previously returned by ``config_get`` or ``config_last_error``.
+/// Passing ``null`` is a no-op.
+///
+/// # Safety
+/// ``s`` must be either null or a pointer previously returned by ``config_get`` or
+/// ``config_last_error``, not already freed, and not used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn config_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        unsafe { drop(CString::from_raw(s)); }
+    }
+}
+
+/// # Last error
+/// Returns a newly allocated copy of the last error message recorded on this thread by
+/// ``config_reader_read``, or ``null`` if there is none. The returned string must be
+/// released with ``config_string_free``.
+#[no_mangle]
+pub extern "C" fn config_last_error() -> *mut c_char {
+    LAST_ERROR.with(|cell| match cell.borrow().as_ref() {
+        Some(msg) => msg.clone().into_raw(),
+        None => ptr::null_mut(),
+    })
+}
@@ -7,27 +7,111 @@ use crate::errors::ConfigValueError::{self, *};
 pub struct ConfigurationItem {
     pub key: String,
     pub value: String,
+
+    /// The file or path the value was loaded from.
+    /// Populated by the parser, and carried over when a layer overrides the value,
+    /// so the winning source of a key can always be traced back.
+    pub origin: Option<String>,
 }
 
 impl ConfigurationItem {
     /// # New ConfigurationItem
-    /// Create an instance of the ConfigurationItem struct with ``key`` and
-    /// ``value`` filled out
-    pub fn new(key: String, value: String) -> ConfigurationItem {
+    /// Create an instance of the ConfigurationItem struct with ``key``,
+    /// ``value`` and ``origin`` filled out
+    pub fn new(key: String, value: String, origin: Option<String>) -> ConfigurationItem {
         ConfigurationItem {
             key,
             value,
+            origin,
         }
     }
 
     pub fn get(&self) -> String { self.value.clone() }
     pub fn as_str(&self) -> &str { self.value.as_str() }
-    pub fn as_i32(&self) -> i32 { self.value.parse::<i32>().unwrap() }
-    pub fn as_u32(&self) -> u32 { self.value.parse::<u32>().unwrap() }
-    pub fn as_f32(&self) -> f32 { self.value.parse::<f32>().unwrap() }
-    pub fn as_i64(&self) -> i64 { self.value.parse::<i64>().unwrap() }
-    pub fn as_u64(&self) -> u64 { self.value.parse::<u64>().unwrap() }
-    pub fn as_f64(&self) -> f64 { self.value.parse::<f64>().unwrap() }
+    pub fn as_i32(&self) -> i32 { self.try_as_i32().unwrap() }
+    pub fn as_u32(&self) -> u32 { self.try_as_u32().unwrap() }
+    pub fn as_f32(&self) -> f32 { self.try_as_f32().unwrap() }
+    pub fn as_i64(&self) -> i64 { self.try_as_i64().unwrap() }
+    pub fn as_u64(&self) -> u64 { self.try_as_u64().unwrap() }
+    pub fn as_f64(&self) -> f64 { self.try_as_f64().unwrap() }
+
+    /// # Try as i32
+    /// Parse the value as an ``i32``, without panicking on malformed input
+    pub fn try_as_i32(&self) -> Result<i32, ConfigValueError> {
+        self.value.parse::<i32>().map_err(|_| InvalidIntValue)
+    }
+
+    /// # Try as u32
+    /// Parse the value as a ``u32``, without panicking on malformed input
+    pub fn try_as_u32(&self) -> Result<u32, ConfigValueError> {
+        self.value.parse::<u32>().map_err(|_| InvalidIntValue)
+    }
+
+    /// # Try as f32
+    /// Parse the value as an ``f32``, without panicking on malformed input
+    pub fn try_as_f32(&self) -> Result<f32, ConfigValueError> {
+        self.value.parse::<f32>().map_err(|_| InvalidFloatValue)
+    }
+
+    /// # Try as i64
+    /// Parse the value as an ``i64``, without panicking on malformed input
+    pub fn try_as_i64(&self) -> Result<i64, ConfigValueError> {
+        self.value.parse::<i64>().map_err(|_| InvalidIntValue)
+    }
+
+    /// # Try as u64
+    /// Parse the value as a ``u64``, without panicking on malformed input
+    pub fn try_as_u64(&self) -> Result<u64, ConfigValueError> {
+        self.value.parse::<u64>().map_err(|_| InvalidIntValue)
+    }
+
+    /// # Try as f64
+    /// Parse the value as an ``f64``, without panicking on malformed input
+    pub fn try_as_f64(&self) -> Result<f64, ConfigValueError> {
+        self.value.parse::<f64>().map_err(|_| InvalidFloatValue)
+    }
+
+    /// # As i32 (graceful)
+    /// Similar to ``as_i32`` but doesn't require unwrapping.
+    /// Instead, invalid syntax falls back to ``default``
+    pub fn as_i32_grf(&self, default: i32) -> i32 {
+        self.try_as_i32().unwrap_or(default)
+    }
+
+    /// # As u32 (graceful)
+    /// Similar to ``as_u32`` but doesn't require unwrapping.
+    /// Instead, invalid syntax falls back to ``default``
+    pub fn as_u32_grf(&self, default: u32) -> u32 {
+        self.try_as_u32().unwrap_or(default)
+    }
+
+    /// # As f32 (graceful)
+    /// Similar to ``as_f32`` but doesn't require unwrapping.
+    /// Instead, invalid syntax falls back to ``default``
+    pub fn as_f32_grf(&self, default: f32) -> f32 {
+        self.try_as_f32().unwrap_or(default)
+    }
+
+    /// # As i64 (graceful)
+    /// Similar to ``as_i64`` but doesn't require unwrapping.
+    /// Instead, invalid syntax falls back to ``default``
+    pub fn as_i64_grf(&self, default: i64) -> i64 {
+        self.try_as_i64().unwrap_or(default)
+    }
+
+    /// # As u64 (graceful)
+    /// Similar to ``as_u64`` but doesn't require unwrapping.
+    /// Instead, invalid syntax falls back to ``default``
+    pub fn as_u64_grf(&self, default: u64) -> u64 {
+        self.try_as_u64().unwrap_or(default)
+    }
+
+    /// # As f64 (graceful)
+    /// Similar to ``as_f64`` but doesn't require unwrapping.
+    /// Instead, invalid syntax falls back to ``default``
+    pub fn as_f64_grf(&self, default: f64) -> f64 {
+        self.try_as_f64().unwrap_or(default)
+    }
 
     /// # As bool
     /// Parse the value as a boolean.
@@ -117,6 +201,7 @@ impl Group {
         self.get(key).unwrap_or(ConfigurationItem {
             key: key.to_string(),
             value: fallback.to_string(),
+            origin: None,
         }).value
     }
 }
@@ -1,6 +1,13 @@
 mod errors;
 mod config;
 mod group;
+mod ffi;
+
+#[cfg(feature = "serde")]
+mod serde_support;
+
+#[cfg(feature = "serde")]
+pub use crate::serde_support::DeserializeError;
 
 use std::{
     fs::File,
@@ -8,8 +15,8 @@ use std::{
         BufReader,
         BufRead,
     },
-    path::Path,
-    collections::HashMap,
+    path::{Path, PathBuf},
+    collections::{HashMap, HashSet},
 };
 
 use regex::Regex;
@@ -56,6 +63,11 @@ pub enum StringStrictnessBehavior {
 pub struct Options {
     pub string_strictness: StringStrictness,
     pub string_strictness_behavior: StringStrictnessBehavior,
+
+    /// When set, environment variables are looked up to override parsed values.
+    /// For a group ``database`` and key ``host``, prefix ``APP`` looks up ``APP_DATABASE_HOST``
+    /// (group and key joined by ``_``, all uppercased).
+    pub env_prefix: Option<String>,
 }
 
 /// # Get default ``Options`` struct
@@ -64,6 +76,7 @@ pub fn get_default_options() -> Options {
     Options {
         string_strictness: Loose,
         string_strictness_behavior: Ignore,
+        env_prefix: None,
     }
 }
 
@@ -80,15 +93,34 @@ impl ConfigReader {
         let opts: Options = options.unwrap_or(get_default_options());
 
         let path: &Path = Path::new(filename);
-        if !path.exists() {
-            return Err(FileNotFound);
+        let mut config: Config = read_file(path, &opts, &HashSet::new())?;
+
+        if let Some(prefix) = &opts.env_prefix {
+            apply_env_overlay(&mut config, prefix);
         }
 
-        let reader = BufReader::new(
-            File::open(path).expect("Cannot open config file")
-        );
+        Ok(config)
+    }
 
-        parse_config_file(reader, &opts)
+    /// # Read layered configuration files
+    /// Load several configuration files, given by ``paths``, in order. Later files
+    /// override keys within the same group, while keys not mentioned in a later file
+    /// are left untouched. This lets a base configuration be combined with one or more
+    /// per-environment override files.
+    pub fn read_layered(paths: &[&str], options: Option<Options>) -> Result<Config, ConfigReadError> {
+        let opts: Options = options.unwrap_or(get_default_options());
+
+        let mut config: Config = Config {
+            map: HashMap::new(),
+            cursor: None,
+        };
+
+        for path in paths {
+            let layer: Config = Self::read(path, Some(opts.clone()))?;
+            config.merge(layer);
+        }
+
+        Ok(config)
     }
 }
 
@@ -103,6 +135,7 @@ struct LineSyntaxRegex {
     pub has_quotes: Regex,
     pub has_whitespace: Regex,
     pub non_string_type: Regex,
+    pub include: Regex,
 }
 
 /// # Get ``LineSyntaxRegex``
@@ -116,9 +149,45 @@ fn get_line_syntax_regex() -> LineSyntaxRegex {
         has_quotes: Regex::new(r#"^"(.*?)"$"#).unwrap(),
         has_whitespace: Regex::new(r"\s+").unwrap(),
         non_string_type: Regex::new(r"^([0-9]+|true|false|yes|no|on|off)$").unwrap(),
+        include: Regex::new(r"^(?:%include\s+(.+)|include\s*=\s*(.+))$").unwrap(),
     }
 }
 
+/// # Read file
+/// Open the configuration file at ``path`` and parse it, following any ``include``
+/// directives it contains relative to its own directory. ``ancestors`` holds the
+/// canonical paths of the files currently being parsed on the way down to this one
+/// (the include *stack*, not every file seen so far), so a file that (transitively)
+/// includes itself is reported as ``ConfigReadError::IncludeCycle`` instead of recursing
+/// forever, while the same file being included more than once from unrelated branches
+/// (a "diamond" include) is not mistaken for a cycle.
+fn read_file(
+    path: &Path,
+    options: &Options,
+    ancestors: &HashSet<PathBuf>,
+) -> Result<Config, ConfigReadError> {
+    if !path.exists() {
+        return Err(FileNotFound);
+    }
+
+    let canonical: PathBuf = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if ancestors.contains(&canonical) {
+        return Err(IncludeCycle);
+    }
+
+    let mut ancestors: HashSet<PathBuf> = ancestors.clone();
+    ancestors.insert(canonical);
+
+    let reader = BufReader::new(
+        File::open(path).expect("Cannot open config file")
+    );
+
+    let base_dir: &Path = path.parent().unwrap_or_else(|| Path::new("."));
+    let origin: String = path.to_string_lossy().to_string();
+
+    parse_config_file(reader, options, &origin, base_dir, &ancestors)
+}
+
 /// # Parse config file
 /// If a file is successfully loaded, we will parse an instance of the ***Config***
 /// struct, which consists of the HashMap under the hood.
@@ -128,6 +197,9 @@ fn get_line_syntax_regex() -> LineSyntaxRegex {
 fn parse_config_file(
     file: BufReader<File>,
     options: &Options,
+    origin: &str,
+    base_dir: &Path,
+    ancestors: &HashSet<PathBuf>,
 ) -> Result<Config, ConfigReadError> {
     let regex: LineSyntaxRegex = get_line_syntax_regex();
     let mut config: Config = Config {
@@ -135,11 +207,21 @@ fn parse_config_file(
         cursor: None,
     };
 
-    for line in file.lines() {
+    for (line_number, line) in file.lines().enumerate() {
         let ln: String = line.unwrap().trim().to_string();
 
         if !is_line_valid(&ln, &regex) {
-            return Err(InvalidSyntaxOnLine);
+            return Err(InvalidSyntax {
+                line: line_number + 1,
+                content: ln,
+            });
+        }
+
+        if regex.include.is_match(&ln) {
+            let target: String = regex.include.replace(&ln, "$1$2").trim().to_string();
+            let included: Config = read_file(&base_dir.join(&target), options, ancestors)?;
+            config.merge(included);
+            continue;
         }
 
         if regex.group.is_match(&ln) {
@@ -152,6 +234,7 @@ fn parse_config_file(
                     config.insert(ConfigurationItem{
                         key: e[0].clone(),
                         value: e[1].clone(),
+                        origin: Some(origin.to_string()),
                     });
                 },
                 _ => {
@@ -207,6 +290,24 @@ fn is_line_valid(
         || regex.group.is_match(line)
         || regex.empty_line.is_match(line)
         || regex.item_any.is_match(line)
+        || regex.include.is_match(line)
+}
+
+/// # Apply environment variable overlay
+/// For every item in every group, look up ``PREFIX_GROUP_KEY`` (uppercased, joined by
+/// ``_``) and, if the variable is set, replace the parsed value with it. The item's
+/// ``origin`` is updated to the variable name so the override is traceable.
+fn apply_env_overlay(config: &mut Config, prefix: &str) {
+    for (group_name, group) in config.map.iter_mut() {
+        for item in group.pairs.values_mut() {
+            let var_name = format!("{}_{}_{}", prefix, group_name, item.key).to_uppercase();
+
+            if let Ok(value) = std::env::var(&var_name) {
+                item.value = value;
+                item.origin = Some(var_name);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -216,7 +317,10 @@ mod tests {
     #[test]
     fn invalid_file_syntax() {
         assert_eq!(
-            Err(InvalidSyntaxOnLine),
+            Err(InvalidSyntax {
+                line: 4,
+                content: "not a valid line at all".to_string(),
+            }),
             ConfigReader::read("./test/test-config-invalid-syntax.txt", None)
         );
     }
@@ -359,4 +463,182 @@ mod tests {
 
         assert!(bools.get("invalid").unwrap().as_bool().is_err());
     }
+
+    #[test]
+    fn ffi_reads_config_and_gets_value() {
+        use std::ffi::{CStr, CString};
+        use crate::ffi::{config_free, config_get, config_reader_read, config_string_free};
+
+        let path = CString::new("./test/test-config.txt").unwrap();
+        let group = CString::new("group").unwrap();
+        let key = CString::new("property").unwrap();
+
+        unsafe {
+            let cfg_ptr = config_reader_read(path.as_ptr());
+            assert!(!cfg_ptr.is_null());
+
+            let value_ptr = config_get(cfg_ptr, group.as_ptr(), key.as_ptr());
+            assert!(!value_ptr.is_null());
+
+            let value = CStr::from_ptr(value_ptr).to_str().unwrap().to_string();
+            assert_eq!("value", value);
+
+            config_string_free(value_ptr);
+            config_free(cfg_ptr);
+        }
+    }
+
+    #[test]
+    fn ffi_missing_file_sets_last_error() {
+        use std::ffi::{CStr, CString};
+        use crate::ffi::{config_last_error, config_reader_read, config_string_free};
+
+        let path = CString::new("./test/does-not-exist.txt").unwrap();
+
+        unsafe {
+            let cfg_ptr = config_reader_read(path.as_ptr());
+            assert!(cfg_ptr.is_null());
+
+            let err_ptr = config_last_error();
+            assert!(!err_ptr.is_null());
+
+            let message = CStr::from_ptr(err_ptr).to_str().unwrap().to_string();
+            assert!(message.contains("not found"));
+
+            config_string_free(err_ptr);
+        }
+    }
+
+    #[test]
+    fn try_as_numeric_succeeds_and_fails() {
+        let cfg: Config = ConfigReader::read("./test/test-config.txt", None).unwrap();
+
+        let number = cfg.group("group").unwrap().get("underscore_value").unwrap();
+        assert_eq!(25i32, number.try_as_i32().unwrap());
+        assert_eq!(25u32, number.try_as_u32().unwrap());
+        assert_eq!(25f32, number.try_as_f32().unwrap());
+        assert_eq!(25i64, number.try_as_i64().unwrap());
+        assert_eq!(25u64, number.try_as_u64().unwrap());
+        assert_eq!(25f64, number.try_as_f64().unwrap());
+
+        let not_a_number = cfg.group("group").unwrap().get("name").unwrap();
+        assert!(not_a_number.try_as_i32().is_err());
+        assert!(not_a_number.try_as_u32().is_err());
+        assert!(not_a_number.try_as_f32().is_err());
+        assert!(not_a_number.try_as_i64().is_err());
+        assert!(not_a_number.try_as_u64().is_err());
+        assert!(not_a_number.try_as_f64().is_err());
+    }
+
+    #[test]
+    fn numeric_grf_falls_back_on_invalid_value() {
+        let cfg: Config = ConfigReader::read("./test/test-config.txt", None).unwrap();
+        let not_a_number = cfg.group("group").unwrap().get("name").unwrap();
+
+        assert_eq!(-1i32, not_a_number.as_i32_grf(-1));
+        assert_eq!(1u32, not_a_number.as_u32_grf(1));
+        assert_eq!(1.5f32, not_a_number.as_f32_grf(1.5));
+        assert_eq!(-1i64, not_a_number.as_i64_grf(-1));
+        assert_eq!(1u64, not_a_number.as_u64_grf(1));
+        assert_eq!(1.5f64, not_a_number.as_f64_grf(1.5));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_group_and_config() {
+        #[derive(serde::Deserialize)]
+        struct Database {
+            host: String,
+            port: i32,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct App {
+            database: Database,
+        }
+
+        let cfg: Config = ConfigReader::read("./test/test-config-base.txt", None).unwrap();
+
+        let database: Database = cfg.group("database").unwrap().deserialize().unwrap();
+        assert_eq!("localhost", database.host);
+        assert_eq!(5432, database.port);
+
+        let app: App = cfg.deserialize().unwrap();
+        assert_eq!("localhost", app.database.host);
+        assert_eq!(5432, app.database.port);
+    }
+
+    #[test]
+    fn env_overlay_overrides_value_and_records_origin() {
+        std::env::set_var("APP_DATABASE_HOST", "env-host");
+
+        let mut opts: Options = get_default_options();
+        opts.env_prefix = Some("APP".to_string());
+
+        let cfg: Config = ConfigReader::read("./test/test-config-base.txt", Some(opts)).unwrap();
+        let host = cfg.group("database").unwrap().get("host").unwrap();
+
+        assert_eq!("env-host", host.value);
+        assert_eq!(Some("APP_DATABASE_HOST".to_string()), host.origin);
+
+        std::env::remove_var("APP_DATABASE_HOST");
+    }
+
+    #[test]
+    fn read_layered_overrides_matching_keys_only() {
+        let cfg: Config = ConfigReader::read_layered(
+            &["./test/test-config-base.txt", "./test/test-config-override.txt"],
+            None,
+        ).unwrap();
+
+        let database = cfg.group("database").unwrap();
+        assert_eq!("prod.example.com", database.get("host").unwrap().value);
+        assert_eq!("5432", database.get("port").unwrap().value);
+        assert_eq!("myapp", cfg.group("app").unwrap().get("name").unwrap().value);
+    }
+
+    #[test]
+    fn read_layered_tracks_origin_of_winning_value() {
+        let cfg: Config = ConfigReader::read_layered(
+            &["./test/test-config-base.txt", "./test/test-config-override.txt"],
+            None,
+        ).unwrap();
+
+        let database = cfg.group("database").unwrap();
+        assert_eq!(
+            Some("./test/test-config-override.txt".to_string()),
+            database.get("host").unwrap().origin
+        );
+        assert_eq!(
+            Some("./test/test-config-base.txt".to_string()),
+            database.get("port").unwrap().origin
+        );
+    }
+
+    #[test]
+    fn include_directive_merges_config() {
+        let cfg: Config = ConfigReader::read("./test/test-include-base.conf", None).unwrap();
+
+        assert!(cfg.group("group").unwrap().has("base_only"));
+        assert!(cfg.group("group").unwrap().has("extra_only"));
+        assert!(cfg.has_group("extra"));
+    }
+
+    #[test]
+    fn diamond_include_is_not_a_cycle() {
+        let cfg: Config = ConfigReader::read("./test/test-diamond-main.conf", None).unwrap();
+
+        assert!(cfg.has_group("main"));
+        assert!(cfg.has_group("a"));
+        assert!(cfg.has_group("b"));
+        assert!(cfg.has_group("common"));
+    }
+
+    #[test]
+    fn include_cycle_is_detected() {
+        assert_eq!(
+            Err(IncludeCycle),
+            ConfigReader::read("./test/test-cycle-a.conf", None)
+        );
+    }
 }
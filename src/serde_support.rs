@@ -0,0 +1,215 @@
+//! Feature-gated `serde` integration.
+//!
+//! This module lets a ``Group`` be deserialized into any type implementing
+//! ``serde::de::DeserializeOwned``, mapping each ``ConfigurationItem`` to a struct field
+//! and parsing ints/floats/bools with the same rules as ``ConfigurationItem::as_bool``.
+//! A whole ``Config`` can likewise be deserialized into a struct whose fields are
+//! themselves structs, one per group. Only present when the ``serde`` feature is enabled.
+
+use std::fmt;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, Visitor,
+};
+
+use crate::{Config, ConfigurationItem, Group};
+
+/// # Deserialize error
+/// The error returned when a ``Group`` or ``Config`` can't be deserialized into the
+/// requested type, e.g. because a value isn't valid for the target field's type.
+#[derive(Debug)]
+pub struct DeserializeError(String);
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        DeserializeError(msg.to_string())
+    }
+}
+
+impl Group {
+    /// # Deserialize
+    /// Deserialize this ``Group`` into ``T``, mapping each configuration key to a
+    /// same-named field on ``T``. Numbers and booleans are parsed with the same rules
+    /// as ``ConfigurationItem::as_bool``/``try_as_i32``/etc.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, DeserializeError> {
+        T::deserialize(GroupDeserializer(self))
+    }
+}
+
+impl Config {
+    /// # Deserialize
+    /// Deserialize this ``Config`` into ``T``, mapping each group name to a same-named
+    /// field on ``T`` whose type is itself deserialized the way ``Group::deserialize`` does.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, DeserializeError> {
+        T::deserialize(ConfigDeserializer(self))
+    }
+}
+
+struct GroupDeserializer<'a>(&'a Group);
+
+impl<'de, 'a> Deserializer<'de> for GroupDeserializer<'a> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(ItemMapAccess {
+            iter: self.0.pairs.values(),
+            value: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct struct enum identifier ignored_any
+    }
+}
+
+struct ItemMapAccess<'a, I: Iterator<Item = &'a ConfigurationItem>> {
+    iter: I,
+    value: Option<&'a ConfigurationItem>,
+}
+
+impl<'de, 'a, I: Iterator<Item = &'a ConfigurationItem>> MapAccess<'de> for ItemMapAccess<'a, I> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.iter.next() {
+            Some(item) => {
+                self.value = Some(item);
+                seed.deserialize(item.key.clone().into_deserializer()).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        let item = self.value.take().expect("next_value_seed called before next_key_seed");
+        seed.deserialize(ValueDeserializer(item))
+    }
+}
+
+/// # Value deserializer
+/// Deserializes a single ``ConfigurationItem``'s value, parsing it as the type the
+/// visitor asks for using the same rules as the fallible `try_as_*`/`as_bool` family.
+struct ValueDeserializer<'a>(&'a ConfigurationItem);
+
+macro_rules! deserialize_parsed {
+    ($method:ident, $visit:ident, $ty:ty) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            let parsed: $ty = self.0.value.parse().map_err(|_| {
+                DeserializeError(format!(
+                    "invalid value \"{}\" for key \"{}\"",
+                    self.0.value, self.0.key
+                ))
+            })?;
+            visitor.$visit(parsed)
+        }
+    };
+}
+
+impl<'de, 'a> Deserializer<'de> for ValueDeserializer<'a> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0.value.clone())
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let parsed = self.0.as_bool().map_err(|_| {
+            DeserializeError(format!(
+                "invalid boolean value \"{}\" for key \"{}\"",
+                self.0.value, self.0.key
+            ))
+        })?;
+        visitor.visit_bool(parsed)
+    }
+
+    deserialize_parsed!(deserialize_i8, visit_i8, i8);
+    deserialize_parsed!(deserialize_i16, visit_i16, i16);
+    deserialize_parsed!(deserialize_i32, visit_i32, i32);
+    deserialize_parsed!(deserialize_i64, visit_i64, i64);
+    deserialize_parsed!(deserialize_u8, visit_u8, u8);
+    deserialize_parsed!(deserialize_u16, visit_u16, u16);
+    deserialize_parsed!(deserialize_u32, visit_u32, u32);
+    deserialize_parsed!(deserialize_u64, visit_u64, u64);
+    deserialize_parsed!(deserialize_f32, visit_f32, f32);
+    deserialize_parsed!(deserialize_f64, visit_f64, f64);
+
+    serde::forward_to_deserialize_any! {
+        char bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct ConfigDeserializer<'a>(&'a Config);
+
+impl<'de, 'a> Deserializer<'de> for ConfigDeserializer<'a> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(GroupMapAccess {
+            iter: self.0.map.iter(),
+            value: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct struct enum identifier ignored_any
+    }
+}
+
+struct GroupMapAccess<'a, I: Iterator<Item = (&'a String, &'a Group)>> {
+    iter: I,
+    value: Option<&'a Group>,
+}
+
+impl<'de, 'a, I: Iterator<Item = (&'a String, &'a Group)>> MapAccess<'de> for GroupMapAccess<'a, I> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.iter.next() {
+            Some((name, group)) => {
+                self.value = Some(group);
+                seed.deserialize(name.clone().into_deserializer()).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        let group = self.value.take().expect("next_value_seed called before next_key_seed");
+        seed.deserialize(GroupDeserializer(group))
+    }
+}